@@ -0,0 +1,22 @@
+/// Errors that can occur while applying a single `fix_*` function to an ABC inventory listing.
+#[derive(Debug, thiserror::Error)]
+pub enum FixError {
+    /// The UI automation call into Client4 itself failed.
+    #[error(transparent)]
+    Automation(#[from] abc_uiautomation::Error),
+
+    /// An exported amount was denominated in a currency other than ABC's configured base
+    /// currency, so it was not safe to write into ABC's text boxes.
+    #[error("sku {sku}: expected an amount in {expected}, but the export reported {found}")]
+    CurrencyMismatch {
+        sku: String,
+        expected: String,
+        found: String,
+    },
+
+    /// The group code resolved for a sku is not one of ABC's known group codes. This means the
+    /// mapping table itself is wrong, since unmapped categories already fall back to
+    /// [`crate::group_mapping::DEFAULT_ABC_GROUP`].
+    #[error("sku {sku}: resolved group `{group}` is not a known ABC group")]
+    UnknownGroup { sku: String, group: String },
+}
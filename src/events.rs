@@ -0,0 +1,57 @@
+use ean13::Ean13;
+
+use crate::money::Money;
+
+/// A single, typed mutation made to an ABC inventory listing by one of the `fix_*` functions in
+/// [`crate::fixers`].
+///
+/// Every variant carries the value that was in ABC before the change, alongside the value that
+/// was written, except [`ChangeEvent::AltSkuAdded`], which is only produced for a slot that was
+/// empty and so carries no prior value. That makes the journal produced by [`crate::fixers::write_logs`] a record of what
+/// actually happened during a run (rather than a dump of the end state), and is the foundation
+/// for a future "undo last run" command.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event")]
+pub enum ChangeEvent {
+    /// The order of an item's UPCs was changed so that the vendor's UPC is primary in ABC.
+    UpcReordered {
+        sku: String,
+        before: Vec<Ean13>,
+        after: Vec<Ean13>,
+    },
+    /// An item's cost was written or corrected.
+    CostSet {
+        sku: String,
+        old: Option<Money>,
+        new: Money,
+    },
+    /// An item's weight was written or corrected.
+    WeightSet {
+        sku: String,
+        old: Option<String>,
+        new: f64,
+    },
+    /// An item's list/retail price was written or corrected.
+    RetailSet {
+        sku: String,
+        old: Option<Money>,
+        new: Money,
+    },
+    /// An item's group code was written or corrected.
+    GroupSet {
+        sku: String,
+        old: Option<String>,
+        new: String,
+        /// The vendor category the new group code was resolved from.
+        vendor_category: String,
+        /// Whether `vendor_category` had an entry in the mapping table, or the default group
+        /// code was used instead.
+        was_mapped: bool,
+    },
+    /// The vendor SKU was added to an item's list of alternative SKUs.
+    AltSkuAdded {
+        sku: String,
+        field_id: u32,
+        value: String,
+    },
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::product::ExportedProduct;
+
+/// A stable hash of the fields of an [`ExportedProduct`] that actually affect what gets written
+/// to ABC (sku, upc, cost, retail, weight, category).
+pub type Fingerprint = u64;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A fixed, versioned FNV-1a hash. Unlike [`std::collections::hash_map::DefaultHasher`] (whose
+/// algorithm is explicitly not guaranteed to stay the same across Rust releases), this is pinned
+/// so a durable on-disk fingerprint stays valid across rebuilds with a different std/rustc.
+fn fnv1a64(bytes: &[u8]) -> Fingerprint {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Serializes the fields that matter to the `fix_*` functions into a byte string, with `\0`
+/// separators so e.g. `sku="ab", category="c"` can't collide with `sku="a", category="bc"`.
+fn fingerprint_bytes(ex_prod: &ExportedProduct) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    buf.extend_from_slice(ex_prod.sku.as_bytes());
+    buf.push(0);
+    buf.extend_from_slice(ex_prod.upc.to_string().as_bytes());
+    buf.push(0);
+    buf.extend_from_slice(&ex_prod.cost.minor_units().to_le_bytes());
+    buf.extend_from_slice(ex_prod.cost.currency().map_or("", |c| c.code()).as_bytes());
+    buf.push(0);
+    match ex_prod.retail {
+        Some(retail) => {
+            buf.push(1);
+            buf.extend_from_slice(&retail.minor_units().to_le_bytes());
+            buf.extend_from_slice(retail.currency().map_or("", |c| c.code()).as_bytes());
+        }
+        None => buf.push(0),
+    }
+    buf.push(0);
+    match ex_prod.weight {
+        Some(weight) => {
+            buf.push(1);
+            buf.extend_from_slice(&weight.to_bits().to_le_bytes());
+        }
+        None => buf.push(0),
+    }
+    buf.push(0);
+    buf.extend_from_slice(ex_prod.category.as_bytes());
+
+    buf
+}
+
+/// Computes a [`Fingerprint`] for `ex_prod` from the fields that matter to the `fix_*` functions.
+pub fn fingerprint_of(ex_prod: &ExportedProduct) -> Fingerprint {
+    fnv1a64(&fingerprint_bytes(ex_prod))
+}
+
+/// A durable `sku -> fingerprint` sidecar map, persisted between runs so that re-running the tool
+/// over an unchanged vendor export can skip products that haven't changed instead of re-driving
+/// every UI action against a flaky Client4 window.
+///
+/// # Invariant
+/// A product's entry must only be updated via [`FingerprintCache::record_success`] *after* every
+/// `fix_*` call for that product has succeeded. If a run fails partway through a product, its
+/// stale (or missing) entry is left in place, which forces the product to be reprocessed on the
+/// next run.
+#[derive(Debug, Clone, Default)]
+pub struct FingerprintCache {
+    fingerprints: HashMap<String, Fingerprint>,
+}
+
+impl FingerprintCache {
+    /// Loads a cache from a file of `sku,fingerprint` lines, written by [`FingerprintCache::save`].
+    /// A missing file is treated as an empty cache, since that's what a first run looks like.
+    ///
+    /// # Errors
+    /// Forwards any [`std::io::Error`] other than [`std::io::ErrorKind::NotFound`].
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+
+        let mut fingerprints = HashMap::new();
+        for line in contents.lines() {
+            if let Some((sku, hash)) = line.split_once(',') {
+                if let Ok(hash) = hash.trim().parse::<Fingerprint>() {
+                    fingerprints.insert(sku.trim().to_string(), hash);
+                }
+            }
+        }
+        Ok(FingerprintCache { fingerprints })
+    }
+
+    /// Persists the cache to `path` as `sku,fingerprint` lines.
+    ///
+    /// # Errors
+    /// Forwards any [`std::io::Error`] resulting from failing to write `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let contents = self
+            .fingerprints
+            .iter()
+            .map(|(sku, hash)| format!("{sku},{hash}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(path, contents)
+    }
+
+    /// Returns `true` when `ex_prod` should be skipped: it isn't new, hasn't changed since the
+    /// last successful run, and `force` hasn't been set to ignore the cache.
+    pub fn is_unchanged(&self, ex_prod: &ExportedProduct, force: bool) -> bool {
+        if force {
+            return false;
+        }
+        self.fingerprints.get(&ex_prod.sku) == Some(&fingerprint_of(ex_prod))
+    }
+
+    /// Records that every `fix_*` call for `ex_prod` succeeded, so the next run can skip it if it
+    /// hasn't changed. Must only be called after the whole fix sequence for this product succeeds.
+    pub fn record_success(&mut self, ex_prod: &ExportedProduct) {
+        self.fingerprints
+            .insert(ex_prod.sku.clone(), fingerprint_of(ex_prod));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::{Money, ABC_BASE_CURRENCY};
+    use rust_decimal::Decimal;
+
+    fn product(sku: &str) -> ExportedProduct {
+        ExportedProduct {
+            sku: sku.to_string(),
+            upc: "0012345678905".parse().expect("valid EAN-13 test fixture"),
+            desc: "Widget".to_string(),
+            weight: Some(1.5),
+            cost: Money::new(Decimal::new(999, 2), ABC_BASE_CURRENCY)
+                .expect("valid money test fixture"),
+            retail: None,
+            category: "widgets".to_string(),
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_deterministic() {
+        let a = product("SKU1");
+        let b = product("SKU1");
+        assert_eq!(fingerprint_of(&a), fingerprint_of(&b));
+    }
+
+    #[test]
+    fn fingerprint_changes_when_a_tracked_field_changes() {
+        let base = product("SKU1");
+        let mut changed = product("SKU1");
+        changed.cost = Money::new(Decimal::new(1099, 2), ABC_BASE_CURRENCY).unwrap();
+
+        assert_ne!(fingerprint_of(&base), fingerprint_of(&changed));
+    }
+
+    #[test]
+    fn is_unchanged_is_true_only_after_recording_a_matching_fingerprint() {
+        let ex_prod = product("SKU1");
+        let mut cache = FingerprintCache::default();
+
+        assert!(!cache.is_unchanged(&ex_prod, false));
+
+        cache.record_success(&ex_prod);
+        assert!(cache.is_unchanged(&ex_prod, false));
+    }
+
+    #[test]
+    fn is_unchanged_is_false_once_a_tracked_field_changes() {
+        let mut ex_prod = product("SKU1");
+        let mut cache = FingerprintCache::default();
+        cache.record_success(&ex_prod);
+
+        ex_prod.weight = Some(2.0);
+
+        assert!(!cache.is_unchanged(&ex_prod, false));
+    }
+
+    #[test]
+    fn force_always_reports_changed() {
+        let ex_prod = product("SKU1");
+        let mut cache = FingerprintCache::default();
+        cache.record_success(&ex_prod);
+
+        assert!(!cache.is_unchanged(&ex_prod, true));
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let mut cache = FingerprintCache::default();
+        cache.record_success(&product("SKU1"));
+        cache.record_success(&product("SKU2"));
+
+        let path = std::env::temp_dir().join(format!(
+            "product-crossref-fingerprint-cache-test-{:?}",
+            std::thread::current().id()
+        ));
+        cache.save(&path).expect("save should succeed");
+        let loaded = FingerprintCache::load(&path).expect("load should succeed");
+        let _ = fs::remove_file(&path);
+
+        assert!(loaded.is_unchanged(&product("SKU1"), false));
+        assert!(loaded.is_unchanged(&product("SKU2"), false));
+    }
+
+    #[test]
+    fn load_of_a_missing_file_is_an_empty_cache() {
+        let path = std::env::temp_dir().join("product-crossref-fingerprint-cache-does-not-exist");
+        let _ = fs::remove_file(&path);
+
+        let cache = FingerprintCache::load(&path).expect("missing file should load as empty");
+
+        assert!(!cache.is_unchanged(&product("SKU1"), false));
+    }
+}
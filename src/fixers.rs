@@ -1,19 +1,21 @@
 use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use abc_product::AbcProduct;
-use abc_uiautomation::{
-    inventory::{clear_upc, set_upc},
-    read_text_box_value, set_text_box_value, UIElement,
-};
 
-use crate::product::{DuplicateProducts, ExportedProduct};
+use crate::error::FixError;
+use crate::events::ChangeEvent;
+use crate::group_mapping::{GroupMapping, DEFAULT_ABC_GROUP, KNOWN_ABC_GROUPS};
+use crate::inventory_writer::InventoryWriter;
+use crate::money::{Money, ABC_BASE_CURRENCY};
+use crate::product::{DuplicateProducts, ExportedProduct, ExportedProductError};
 
-/// Controls ABC Client4 window to reorder the UPCs of an inventory item so that the primary UPC
-/// from a vendor exported list is the primary UPC in ABC. IE the last UPC in ABC will match the
-/// UPC from the export
+/// Controls an ABC inventory listing to reorder its UPCs so that the primary UPC from a vendor
+/// exported list is the primary UPC in ABC. IE the last UPC in ABC will match the UPC from the
+/// export
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
 ///
@@ -22,118 +24,208 @@ use crate::product::{DuplicateProducts, ExportedProduct};
 /// * Failing to clear exising UPCs
 /// * Failing to set any new UPCs
 pub fn fix_upc(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     abc_prod: &AbcProduct,
     ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
-    clear_upc(inventory_window, true)?;
+) -> Result<ChangeEvent, FixError> {
+    let before = abc_prod.upcs();
+    inventory_window.clear_upcs()?;
+    let mut after = Vec::with_capacity(before.len());
     for upc in abc_prod.upcs() {
         if upc != ex_prod.upc {
-            set_upc(inventory_window, upc)?;
+            inventory_window.set_upc(upc)?;
+            after.push(upc);
         }
     }
-    set_upc(inventory_window, ex_prod.upc)?;
-    Ok(())
+    inventory_window.set_upc(ex_prod.upc)?;
+    after.push(ex_prod.upc);
+    Ok(ChangeEvent::UpcReordered {
+        sku: ex_prod.sku.clone(),
+        before,
+        after,
+    })
 }
 
-/// Controls ABC Client4 window to add or fix the weight value in an ABC inventory listing
+/// Controls an ABC inventory listing to add or fix the weight value in an ABC inventory listing
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
 ///
+/// Returns `None` when the export has no weight, since nothing is written to ABC in that case.
+///
 /// # Errors
 /// Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the weight value in ABC
 pub fn fix_weight(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     _abc_prod: &AbcProduct,
     ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
-    if let Some(weight) = ex_prod.weight {
-        set_text_box_value(inventory_window, 15, weight.to_string())?;
-    }
-    Ok(())
+) -> Result<Option<ChangeEvent>, FixError> {
+    let Some(weight) = ex_prod.weight else {
+        return Ok(None);
+    };
+    let old = inventory_window.read_text_box(15)?;
+    inventory_window.set_text_box(15, &weight.to_string())?;
+    Ok(Some(ChangeEvent::WeightSet {
+        sku: ex_prod.sku.clone(),
+        old: if old.is_empty() { None } else { Some(old) },
+        new: weight,
+    }))
 }
 
-/// Controls ABC Client4 window to add or fix the cost value in an ABC inventory listing
+/// Controls an ABC inventory listing to add or fix the cost value in an ABC inventory listing
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
 ///
 /// # Errors
 /// Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the cost value in ABC
 pub fn fix_cost(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     _abc_prod: &AbcProduct,
     ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
-    set_text_box_value(inventory_window, 26, ex_prod.cost.to_string())?;
-    Ok(())
+) -> Result<ChangeEvent, FixError> {
+    if ex_prod.cost.currency() != Some(ABC_BASE_CURRENCY) {
+        return Err(FixError::CurrencyMismatch {
+            sku: ex_prod.sku.clone(),
+            expected: ABC_BASE_CURRENCY.to_string(),
+            found: ex_prod
+                .cost
+                .currency()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        });
+    }
+    let old = inventory_window.read_text_box(26)?;
+    inventory_window.set_text_box(26, &ex_prod.cost.to_decimal_string())?;
+    Ok(ChangeEvent::CostSet {
+        sku: ex_prod.sku.clone(),
+        old: old
+            .parse()
+            .ok()
+            .and_then(|d| Money::new(d, ABC_BASE_CURRENCY).ok()),
+        new: ex_prod.cost,
+    })
 }
 
-/// Controls ABC Client4 window to add or fix the list/retail value in an ABC inventory listing
+/// Controls an ABC inventory listing to add or fix the list/retail value in an ABC inventory
+/// listing
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
 ///
+/// Returns `None` when the export has no retail price, since nothing is written to ABC in that
+/// case.
+///
 /// # Errors
 /// Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the retail value in ABC
 pub fn fix_retail(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     _abc_prod: &AbcProduct,
     ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
-    if let Some(retail) = ex_prod.retail {
-        set_text_box_value(inventory_window, 25, retail.to_string())?;
+) -> Result<Option<ChangeEvent>, FixError> {
+    let Some(retail) = ex_prod.retail else {
+        return Ok(None);
+    };
+    if retail.currency() != Some(ABC_BASE_CURRENCY) {
+        return Err(FixError::CurrencyMismatch {
+            sku: ex_prod.sku.clone(),
+            expected: ABC_BASE_CURRENCY.to_string(),
+            found: retail
+                .currency()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        });
     }
-    Ok(())
+    let old = inventory_window.read_text_box(25)?;
+    inventory_window.set_text_box(25, &retail.to_decimal_string())?;
+    Ok(Some(ChangeEvent::RetailSet {
+        sku: ex_prod.sku.clone(),
+        old: old
+            .parse()
+            .ok()
+            .and_then(|d| Money::new(d, ABC_BASE_CURRENCY).ok()),
+        new: retail,
+    }))
 }
 
-/// Controls ABC Client4 window to add or fix the group value in an ABC inventory listing
+/// Controls an ABC inventory listing to add or fix the group value in an ABC inventory listing
+///
+/// Looks up `ex_prod.category` in `mapping`, falling back to [`DEFAULT_ABC_GROUP`] when the
+/// category has no entry, and validates that the resolved group is one of
+/// [`KNOWN_ABC_GROUPS`] before writing it.
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
+/// * `mapping` - The vendor category -> ABC group lookup table
 ///
 /// # Errors
-/// Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the group value in ABC
+/// * Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the group value in ABC
+/// * [`FixError::UnknownGroup`] if the resolved group code is not a known ABC group
 pub fn fix_group(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     _abc_prod: &AbcProduct,
-    _ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
-    set_text_box_value(inventory_window, 39, "Z")?;
-    Ok(())
+    ex_prod: &ExportedProduct,
+    mapping: &GroupMapping,
+) -> Result<ChangeEvent, FixError> {
+    let (group, was_mapped) = match mapping.resolve(&ex_prod.category) {
+        Some(group) => (group.to_string(), true),
+        None => (DEFAULT_ABC_GROUP.to_string(), false),
+    };
+    if !KNOWN_ABC_GROUPS.contains(&group.as_str()) {
+        return Err(FixError::UnknownGroup {
+            sku: ex_prod.sku.clone(),
+            group,
+        });
+    }
+
+    let old = inventory_window.read_text_box(39)?;
+    inventory_window.set_text_box(39, &group)?;
+    Ok(ChangeEvent::GroupSet {
+        sku: ex_prod.sku.clone(),
+        old: if old.is_empty() { None } else { Some(old) },
+        new: group,
+        vendor_category: ex_prod.category.clone(),
+        was_mapped,
+    })
 }
 
 /// Add the vendor sku to an ABC item listing by adding it to the list of alternative skus
 ///
 /// # Arguments
-/// * `inventory_window` - The [`UIElement`] representing the Inventory screen of Client4
+/// * `inventory_window` - The [`InventoryWriter`] representing the Inventory screen of Client4
 /// * `abc_prod` - Represents the ABC item as it exists before manipulation
 /// * `ex_prod` - The product listing that was exported from a vendor
 ///
+/// Returns `None` when fields 35-37 are all already occupied, since there is nowhere to write the
+/// vendor sku and no mutation happens.
+///
 /// # Errors
 /// Forwards any [`abc_uiautomation::Error`]s resulting from failing to set the group value in ABC
 pub fn fix_alt_sku(
-    inventory_window: &UIElement,
+    inventory_window: &dyn InventoryWriter,
     _abc_prod: &AbcProduct,
     ex_prod: &ExportedProduct,
-) -> Result<(), abc_uiautomation::Error> {
+) -> Result<Option<ChangeEvent>, FixError> {
     for i in 35..38 {
-        let spot = read_text_box_value(inventory_window, i)?;
+        let spot = inventory_window.read_text_box(i)?;
         if spot.is_empty() {
-            set_text_box_value(inventory_window, i, &ex_prod.sku)?;
-            break;
+            inventory_window.set_text_box(i, &ex_prod.sku)?;
+            return Ok(Some(ChangeEvent::AltSkuAdded {
+                sku: ex_prod.sku.clone(),
+                field_id: i,
+                value: ex_prod.sku.clone(),
+            }));
         }
     }
-    Ok(())
+    Ok(None)
 }
 
 /// Write log files to enumerate all products that failed to be cross referenced due to one of the
@@ -145,7 +237,13 @@ pub fn fix_alt_sku(
 /// * There is a matching ABC listing, but either the list price or the cost is vastly different,
 /// so it is worth having a human double check it (double_check.txt)
 ///
-/// Also writes a list of products that were successfully matched in ABC (matched_products.txt)
+/// Also writes a list of products that were successfully matched in ABC (matched_products.txt),
+/// an append-only, line-delimited JSON change journal (change_journal_<unix timestamp>.jsonl)
+/// recording every [`ChangeEvent`] produced by the `fix_*` functions during the run, the
+/// distinct vendor categories seen in [`ChangeEvent::GroupSet`] events that fell back to the
+/// default group (unmapped_categories.txt), and the rows that failed
+/// [`ExportedProduct::validate`] along with their reasons (invalid_products.txt), so a single bad
+/// spreadsheet cell doesn't silently corrupt an inventory item.
 ///
 /// # Arguments
 /// * `dups` - The list of [`AbcProduct`]s that share a UPC
@@ -153,14 +251,20 @@ pub fn fix_alt_sku(
 /// * `check` - The list of [`ExportedProduct`]s that have a UPC match but seem to be vastly
 /// different from the matching ABC listing
 /// * `matches` - Lit of [`ExportedProduct`]s that have a good UPC match in ABC and were able to be adjusted in ABC
+/// * `journal` - The [`ChangeEvent`]s produced while fixing `matches`, in the order they occurred
+/// * `invalid` - The [`ExportedProduct`]s that failed validation, paired with the reasons why
 ///
 /// # Errors
-/// Forwards any [`std::io::Error`]s resulting from trying to write any of the log files
+/// Forwards any [`std::io::Error`]s resulting from trying to write any of the log files. Also
+/// returns an error (rather than writing a silently-incomplete journal) if any [`ChangeEvent`]
+/// fails to serialize to JSON.
 pub fn write_logs(
     dups: Vec<&DuplicateProducts>,
     new: Vec<ExportedProduct>,
     check: Vec<ExportedProduct>,
     matches: Vec<ExportedProduct>,
+    journal: Vec<ChangeEvent>,
+    invalid: Vec<(ExportedProduct, Vec<ExportedProductError>)>,
 ) -> std::io::Result<()> {
     fs::write(
         "./duplicate_products.txt",
@@ -184,5 +288,225 @@ pub fn write_logs(
             matches
         ),
     )?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let journal_lines = journal
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?
+        .join("\n");
+    fs::write(
+        format!("./change_journal_{timestamp}.jsonl"),
+        journal_lines,
+    )?;
+
+    let mut unmapped_categories: Vec<&str> = journal
+        .iter()
+        .filter_map(|event| match event {
+            ChangeEvent::GroupSet {
+                vendor_category,
+                was_mapped: false,
+                ..
+            } => Some(vendor_category.as_str()),
+            _ => None,
+        })
+        .collect();
+    unmapped_categories.sort_unstable();
+    unmapped_categories.dedup();
+    fs::write(
+        "./unmapped_categories.txt",
+        format!(
+            "The following vendor categories have no entry in the group mapping table and were \
+written using the default group. Please add them to the mapping table.\n\n{:#?}",
+            unmapped_categories
+        ),
+    )?;
+
+    fs::write(
+        "./invalid_products.txt",
+        format!(
+            "The following rows failed validation and were not sent to ABC. Please fix them in \
+the vendor export and re-run.\n\n{:#?}",
+            invalid
+        ),
+    )?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory_writer::FakeInventoryWriter;
+    use abc_product::AbcProduct;
+    use ean13::Ean13;
+    use std::collections::HashMap;
+
+    fn ean(digits: &str) -> Ean13 {
+        digits.parse().expect("valid EAN-13 test fixture")
+    }
+
+    #[test]
+    fn fix_upc_puts_vendor_upc_last() {
+        let vendor_upc = ean("0012345678905");
+        let existing_upc = ean("0000000000017");
+        // The vendor UPC is first rather than last (primary) in ABC before the fix runs, which is
+        // the mismatch fix_upc exists to correct.
+        let writer = FakeInventoryWriter::with_upcs(vec![vendor_upc, existing_upc]);
+        let abc_prod = AbcProduct::new("SKU1", vec![vendor_upc, existing_upc]);
+        let ex_prod = ExportedProduct {
+            sku: "SKU1".to_string(),
+            upc: vendor_upc,
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: String::new(),
+        };
+
+        let event = fix_upc(&writer, &abc_prod, &ex_prod).expect("fix_upc should succeed");
+
+        assert_eq!(writer.upcs(), vec![existing_upc, vendor_upc]);
+        match event {
+            ChangeEvent::UpcReordered { before, after, .. } => {
+                assert_eq!(before, vec![vendor_upc, existing_upc]);
+                assert_eq!(after, vec![existing_upc, vendor_upc]);
+            }
+            other => panic!("expected UpcReordered, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fix_group_writes_default_group_for_unmapped_category() {
+        let writer = FakeInventoryWriter::new();
+        let abc_prod = AbcProduct::new("SKU1", vec![]);
+        let ex_prod = ExportedProduct {
+            sku: "SKU1".to_string(),
+            upc: ean("0012345678905"),
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: "unmapped-category".to_string(),
+        };
+        let mapping = GroupMapping::default();
+
+        let event = fix_group(&writer, &abc_prod, &ex_prod, &mapping)
+            .expect("fix_group should succeed");
+
+        assert_eq!(writer.text_box(39), "Z");
+        match event {
+            ChangeEvent::GroupSet { was_mapped, .. } => assert!(!was_mapped),
+            other => panic!("expected GroupSet, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fix_group_writes_mapped_group_for_known_category() {
+        let writer = FakeInventoryWriter::new();
+        let abc_prod = AbcProduct::new("SKU1", vec![]);
+        let ex_prod = ExportedProduct {
+            sku: "SKU1".to_string(),
+            upc: ean("0012345678905"),
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: "power-tools".to_string(),
+        };
+        let mapping = GroupMapping::new(HashMap::from([(
+            "power-tools".to_string(),
+            "P".to_string(),
+        )]));
+
+        let event = fix_group(&writer, &abc_prod, &ex_prod, &mapping)
+            .expect("fix_group should succeed");
+
+        assert_eq!(writer.text_box(39), "P");
+        match event {
+            ChangeEvent::GroupSet {
+                new, was_mapped, ..
+            } => {
+                assert_eq!(new, "P");
+                assert!(was_mapped);
+            }
+            other => panic!("expected GroupSet, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fix_group_rejects_a_mapping_to_an_unknown_group() {
+        let writer = FakeInventoryWriter::new();
+        let abc_prod = AbcProduct::new("SKU1", vec![]);
+        let ex_prod = ExportedProduct {
+            sku: "SKU1".to_string(),
+            upc: ean("0012345678905"),
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: "power-tools".to_string(),
+        };
+        let mapping = GroupMapping::new(HashMap::from([(
+            "power-tools".to_string(),
+            "NOT-A-GROUP".to_string(),
+        )]));
+
+        let err = fix_group(&writer, &abc_prod, &ex_prod, &mapping)
+            .expect_err("fix_group should reject an unknown group code");
+
+        assert!(matches!(err, FixError::UnknownGroup { .. }));
+        assert_eq!(writer.text_box(39), "");
+    }
+
+    #[test]
+    fn fix_alt_sku_fills_first_empty_slot() {
+        let writer = FakeInventoryWriter::new().with_text_box(35, "EXISTING");
+        let abc_prod = AbcProduct::new("SKU1", vec![]);
+        let ex_prod = ExportedProduct {
+            sku: "VENDOR-SKU".to_string(),
+            upc: ean("0012345678905"),
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: String::new(),
+        };
+
+        let event = fix_alt_sku(&writer, &abc_prod, &ex_prod).expect("fix_alt_sku should succeed");
+
+        assert!(event.is_some());
+        assert_eq!(writer.text_box(35), "EXISTING");
+        assert_eq!(writer.text_box(36), "VENDOR-SKU");
+        assert_eq!(writer.text_box(37), "");
+    }
+
+    #[test]
+    fn fix_alt_sku_is_a_noop_when_no_slot_is_empty() {
+        let writer = FakeInventoryWriter::new()
+            .with_text_box(35, "A")
+            .with_text_box(36, "B")
+            .with_text_box(37, "C");
+        let abc_prod = AbcProduct::new("SKU1", vec![]);
+        let ex_prod = ExportedProduct {
+            sku: "VENDOR-SKU".to_string(),
+            upc: ean("0012345678905"),
+            desc: "Widget".to_string(),
+            weight: None,
+            cost: Default::default(),
+            retail: None,
+            category: String::new(),
+        };
+
+        let event = fix_alt_sku(&writer, &abc_prod, &ex_prod).expect("fix_alt_sku should succeed");
+
+        assert!(event.is_none());
+        assert_eq!(writer.text_box(35), "A");
+        assert_eq!(writer.text_box(36), "B");
+        assert_eq!(writer.text_box(37), "C");
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The ABC group code written for a vendor category that has no entry in the mapping table.
+pub const DEFAULT_ABC_GROUP: &str = "Z";
+
+/// Every ABC group code the inventory system actually recognizes. A resolved group that isn't in
+/// this list is a bug in the mapping table, not a category the operator forgot to map.
+pub const KNOWN_ABC_GROUPS: &[&str] = &[
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z",
+];
+
+/// A loadable table mapping a vendor's category string to the ABC group code it should write.
+///
+/// Replaces the old behavior of unconditionally writing [`DEFAULT_ABC_GROUP`] for every item,
+/// which threw away any category information present in the vendor export.
+#[derive(Debug, Clone, Default)]
+pub struct GroupMapping {
+    table: HashMap<String, String>,
+}
+
+impl GroupMapping {
+    /// Builds a mapping table directly from `vendor_category -> abc_group` pairs.
+    pub fn new(table: HashMap<String, String>) -> Self {
+        GroupMapping { table }
+    }
+
+    /// Loads a mapping table from a file of `vendor_category,abc_group` lines.
+    ///
+    /// # Errors
+    /// Forwards any [`std::io::Error`] resulting from failing to read `path`.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut table = HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((category, group)) = line.split_once(',') {
+                table.insert(category.trim().to_string(), group.trim().to_string());
+            }
+        }
+        Ok(GroupMapping { table })
+    }
+
+    /// Looks up the ABC group code mapped to `category`, if any.
+    pub fn resolve(&self, category: &str) -> Option<&str> {
+        self.table.get(category).map(String::as_str)
+    }
+}
@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use abc_uiautomation::{
+    inventory::{clear_upc, set_upc},
+    read_text_box_value, set_text_box_value, UIElement,
+};
+use ean13::Ean13;
+
+/// An output port onto an ABC inventory listing.
+///
+/// `fix_*` functions in [`crate::fixers`] are written against this trait instead of a real
+/// [`UIElement`] directly, so the cross-referencing logic can be exercised in `#[test]`s without a
+/// live Client4 window. [`UIElement`] implements it by forwarding to the real `abc_uiautomation`
+/// calls; [`FakeInventoryWriter`] implements it entirely in memory.
+pub trait InventoryWriter {
+    /// Clears every UPC currently listed for the item.
+    fn clear_upcs(&self) -> Result<(), abc_uiautomation::Error>;
+
+    /// Appends a UPC to the end of the item's UPC list, making it the new primary UPC.
+    fn set_upc(&self, upc: Ean13) -> Result<(), abc_uiautomation::Error>;
+
+    /// Writes `value` into the text box identified by `field_id`.
+    fn set_text_box(&self, field_id: u32, value: &str) -> Result<(), abc_uiautomation::Error>;
+
+    /// Reads the current value of the text box identified by `field_id`.
+    fn read_text_box(&self, field_id: u32) -> Result<String, abc_uiautomation::Error>;
+}
+
+impl InventoryWriter for UIElement {
+    fn clear_upcs(&self) -> Result<(), abc_uiautomation::Error> {
+        clear_upc(self, true)
+    }
+
+    fn set_upc(&self, upc: Ean13) -> Result<(), abc_uiautomation::Error> {
+        set_upc(self, upc)
+    }
+
+    fn set_text_box(&self, field_id: u32, value: &str) -> Result<(), abc_uiautomation::Error> {
+        set_text_box_value(self, field_id, value)
+    }
+
+    fn read_text_box(&self, field_id: u32) -> Result<String, abc_uiautomation::Error> {
+        read_text_box_value(self, field_id)
+    }
+}
+
+/// An in-memory [`InventoryWriter`] used to exercise the cross-referencing pipeline in tests
+/// without a real Client4 window.
+#[derive(Debug, Default)]
+pub struct FakeInventoryWriter {
+    upcs: RefCell<Vec<Ean13>>,
+    text_boxes: RefCell<HashMap<u32, String>>,
+}
+
+impl FakeInventoryWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds the writer with the UPCs an ABC listing would already have before a run.
+    pub fn with_upcs(upcs: Vec<Ean13>) -> Self {
+        Self {
+            upcs: RefCell::new(upcs),
+            text_boxes: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Seeds the writer with the text box values an ABC listing would already have before a run.
+    pub fn with_text_box(self, field_id: u32, value: impl Into<String>) -> Self {
+        self.text_boxes
+            .borrow_mut()
+            .insert(field_id, value.into());
+        self
+    }
+
+    pub fn upcs(&self) -> Vec<Ean13> {
+        self.upcs.borrow().clone()
+    }
+
+    pub fn text_box(&self, field_id: u32) -> String {
+        self.text_boxes
+            .borrow()
+            .get(&field_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl InventoryWriter for FakeInventoryWriter {
+    fn clear_upcs(&self) -> Result<(), abc_uiautomation::Error> {
+        self.upcs.borrow_mut().clear();
+        Ok(())
+    }
+
+    fn set_upc(&self, upc: Ean13) -> Result<(), abc_uiautomation::Error> {
+        self.upcs.borrow_mut().push(upc);
+        Ok(())
+    }
+
+    fn set_text_box(&self, field_id: u32, value: &str) -> Result<(), abc_uiautomation::Error> {
+        self.text_boxes
+            .borrow_mut()
+            .insert(field_id, value.to_string());
+        Ok(())
+    }
+
+    fn read_text_box(&self, field_id: u32) -> Result<String, abc_uiautomation::Error> {
+        Ok(self.text_box(field_id))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod error;
+pub mod events;
+pub mod fingerprint;
+pub mod fixers;
+pub mod group_mapping;
+pub mod inventory_writer;
+pub mod money;
+pub mod product;
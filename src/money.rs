@@ -0,0 +1,185 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize};
+
+/// The currency ABC's text boxes are configured to hold. A [`Money`] value denominated in any
+/// other currency is a mistake, not a number to silently reformat and write anyway.
+pub const ABC_BASE_CURRENCY: Currency = Currency([b'U', b'S', b'D']);
+
+/// A three letter ISO-4217 currency code, e.g. `USD` or `CAD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "String")]
+pub struct Currency([u8; 3]);
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.code().to_string()
+    }
+}
+
+impl Currency {
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = MoneyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.trim().to_ascii_uppercase();
+        let bytes = upper.as_bytes();
+        if bytes.len() != 3 || !bytes.iter().all(u8::is_ascii_alphabetic) {
+            return Err(MoneyParseError::InvalidCurrency(s.to_string()));
+        }
+        Ok(Currency([bytes[0], bytes[1], bytes[2]]))
+    }
+}
+
+/// Errors produced while parsing a [`Money`] value out of an exported spreadsheet cell.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MoneyParseError {
+    #[error("`{0}` is not a valid decimal amount")]
+    InvalidAmount(String),
+    #[error("`{0}` is not a valid ISO-4217 currency code")]
+    InvalidCurrency(String),
+    #[error("amount `{0}` is missing a currency code")]
+    MissingCurrency(String),
+}
+
+/// A currency-aware monetary amount, stored as an integer count of minor units (e.g. cents)
+/// alongside its ISO-4217 currency code.
+///
+/// Vendor exports are free to report prices in whatever currency the vendor uses; keeping that
+/// currency attached to the amount all the way through to `fix_cost`/`fix_retail` lets a mismatch
+/// against ABC's configured base currency be caught instead of silently written in as if it were
+/// the same currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub struct Money {
+    minor_units: i64,
+    currency: Option<Currency>,
+}
+
+impl Money {
+    /// Builds a `Money` from a decimal amount and its currency, rounding to two decimal places.
+    pub fn new(amount: Decimal, currency: Currency) -> Result<Self, MoneyParseError> {
+        let minor_units = (amount.round_dp(2) * Decimal::from(100))
+            .to_i64()
+            .ok_or_else(|| MoneyParseError::InvalidAmount(amount.to_string()))?;
+        Ok(Money {
+            minor_units,
+            currency: Some(currency),
+        })
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    /// Formats the amount back into the plain decimal string ABC's text boxes expect, e.g.
+    /// `"12.99"` or `"-0.05"`, discarding the currency.
+    pub fn to_decimal_string(&self) -> String {
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+        let abs_units = self.minor_units.unsigned_abs();
+        format!("{sign}{}.{:02}", abs_units / 100, abs_units % 100)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_decimal_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        let mut parts = raw.trim().splitn(2, char::is_whitespace);
+        let amount_str = parts.next().unwrap_or("").trim();
+        let currency_str = parts.next().unwrap_or("").trim();
+
+        let amount = amount_str
+            .parse::<Decimal>()
+            .map_err(|_| DeError::custom(MoneyParseError::InvalidAmount(amount_str.to_string())))?;
+
+        if currency_str.is_empty() {
+            return Err(DeError::custom(MoneyParseError::MissingCurrency(
+                amount_str.to_string(),
+            )));
+        }
+        let currency = currency_str
+            .parse::<Currency>()
+            .map_err(DeError::custom)?;
+
+        Money::new(amount, currency).map_err(DeError::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_decimal_string_formats_a_positive_amount() {
+        let money = Money::new(Decimal::new(1299, 2), ABC_BASE_CURRENCY).unwrap();
+        assert_eq!(money.to_decimal_string(), "12.99");
+    }
+
+    #[test]
+    fn to_decimal_string_preserves_sign_of_small_negative_amounts() {
+        let money = Money::new(Decimal::new(-5, 2), ABC_BASE_CURRENCY).unwrap();
+        assert_eq!(money.to_decimal_string(), "-0.05");
+    }
+
+    #[test]
+    fn to_decimal_string_formats_zero() {
+        assert_eq!(Money::default().to_decimal_string(), "0.00");
+    }
+
+    #[test]
+    fn new_rounds_to_two_decimal_places() {
+        let money = Money::new(Decimal::new(129949, 4), ABC_BASE_CURRENCY).unwrap();
+        assert_eq!(money.minor_units(), 1299);
+    }
+
+    #[test]
+    fn deserialize_parses_a_valid_amount_and_currency() {
+        let money: Money = serde_json::from_str("\"12.99 USD\"").unwrap();
+        assert_eq!(money.minor_units(), 1299);
+        assert_eq!(money.currency(), Some(ABC_BASE_CURRENCY));
+    }
+
+    #[test]
+    fn deserialize_rejects_a_missing_currency() {
+        let result: Result<Money, _> = serde_json::from_str("\"12.99\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_an_invalid_currency() {
+        let result: Result<Money, _> = serde_json::from_str("\"12.99 U5D\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_an_invalid_amount() {
+        let result: Result<Money, _> = serde_json::from_str("\"not-a-number USD\"");
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,9 @@
 use abc_product::AbcProduct;
 use ean13::Ean13;
-use rust_decimal::Decimal;
 use std::collections::HashMap;
 
+use crate::money::Money;
+
 pub type DuplicateProducts = Vec<AbcProduct>;
 
 pub fn map_upcs(
@@ -31,6 +32,160 @@ pub struct ExportedProduct {
     pub upc: Ean13,
     pub desc: String,
     pub weight: Option<f64>,
-    pub cost: Decimal,
-    pub retail: Option<Decimal>,
+    pub cost: Money,
+    pub retail: Option<Money>,
+    pub category: String,
+}
+
+/// A single problem found while validating an [`ExportedProduct`] row before it is allowed into
+/// the matching pipeline.
+///
+/// There is deliberately no "bad EAN-13 check digit" variant here: `upc` is an [`Ean13`], and
+/// `Ean13`'s own `FromStr`/`Deserialize` impl already rejects a malformed check digit at parse
+/// time, so an `ExportedProduct` can never be holding an invalid one by the time `validate` runs.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ExportedProductError {
+    #[error("sku is empty")]
+    SkuIsEmpty,
+    #[error("description is empty")]
+    DescriptionIsEmpty,
+    #[error("cost must be positive, got {0}")]
+    CostNotPositive(String),
+    #[error("weight must be positive and finite, got {0}")]
+    WeightNotPositive(f64),
+}
+
+impl ExportedProduct {
+    /// Validates the row, accumulating every problem found rather than stopping at the first, so
+    /// one bad spreadsheet cell doesn't hide another.
+    pub fn validate(&self) -> Result<(), Vec<ExportedProductError>> {
+        let mut errors = Vec::new();
+
+        if self.sku.trim().is_empty() {
+            errors.push(ExportedProductError::SkuIsEmpty);
+        }
+        if self.desc.trim().is_empty() {
+            errors.push(ExportedProductError::DescriptionIsEmpty);
+        }
+        if self.cost.minor_units() <= 0 {
+            errors.push(ExportedProductError::CostNotPositive(self.cost.to_string()));
+        }
+        if let Some(weight) = self.weight {
+            if !weight.is_finite() || weight <= 0.0 {
+                errors.push(ExportedProductError::WeightNotPositive(weight));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_product() -> ExportedProduct {
+        ExportedProduct {
+            sku: "SKU1".to_string(),
+            upc: "0012345678905".parse().expect("valid EAN-13 test fixture"),
+            desc: "Widget".to_string(),
+            weight: Some(1.5),
+            cost: Money::new(rust_decimal::Decimal::new(999, 2), crate::money::ABC_BASE_CURRENCY)
+                .expect("valid money test fixture"),
+            retail: None,
+            category: "widgets".to_string(),
+        }
+    }
+
+    #[test]
+    fn valid_product_passes() {
+        assert_eq!(valid_product().validate(), Ok(()));
+    }
+
+    #[test]
+    fn empty_sku_is_rejected() {
+        let product = ExportedProduct {
+            sku: "  ".to_string(),
+            ..valid_product()
+        };
+        assert_eq!(
+            product.validate(),
+            Err(vec![ExportedProductError::SkuIsEmpty])
+        );
+    }
+
+    #[test]
+    fn empty_description_is_rejected() {
+        let product = ExportedProduct {
+            desc: "".to_string(),
+            ..valid_product()
+        };
+        assert_eq!(
+            product.validate(),
+            Err(vec![ExportedProductError::DescriptionIsEmpty])
+        );
+    }
+
+    #[test]
+    fn non_positive_cost_is_rejected() {
+        let product = ExportedProduct {
+            cost: Money::default(),
+            ..valid_product()
+        };
+        assert_eq!(
+            product.validate(),
+            Err(vec![ExportedProductError::CostNotPositive(
+                Money::default().to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn non_positive_weight_is_rejected() {
+        let product = ExportedProduct {
+            weight: Some(0.0),
+            ..valid_product()
+        };
+        assert_eq!(
+            product.validate(),
+            Err(vec![ExportedProductError::WeightNotPositive(0.0)])
+        );
+    }
+
+    #[test]
+    fn non_finite_weight_is_rejected() {
+        for weight in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let product = ExportedProduct {
+                weight: Some(weight),
+                ..valid_product()
+            };
+            let errors = product.validate().expect_err("non-finite weight is invalid");
+            assert!(matches!(
+                errors.as_slice(),
+                [ExportedProductError::WeightNotPositive(_)]
+            ));
+        }
+    }
+
+    #[test]
+    fn validate_accumulates_every_error() {
+        let product = ExportedProduct {
+            sku: "".to_string(),
+            desc: "".to_string(),
+            cost: Money::default(),
+            weight: Some(-1.0),
+            ..valid_product()
+        };
+
+        let errors = product.validate().expect_err("row should be invalid");
+
+        assert_eq!(errors.len(), 4);
+        assert!(errors.contains(&ExportedProductError::SkuIsEmpty));
+        assert!(errors.contains(&ExportedProductError::DescriptionIsEmpty));
+        assert!(errors.contains(&ExportedProductError::WeightNotPositive(-1.0)));
+    }
 }